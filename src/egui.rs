@@ -1,5 +1,6 @@
-use crate::{DiagnosticState, DiagnosticVisualizerState};
+use crate::{DiagnosticState, DiagnosticVisualizerState, Paused};
 use bevy::prelude::*;
+use std::collections::BTreeMap;
 use bevy_egui::{
     egui::{
         epaint::{PathShape, RectShape},
@@ -18,6 +19,7 @@ impl Plugin for DiagnosticVisualizerEguiPlugin {
         }
         app.insert_resource(Style::default())
             .insert_resource(IsOpenState(true))
+            .insert_resource(FlameState(false))
             .add_system_to_stage(CoreStage::PostUpdate, plot_diagnostics_system);
     }
 }
@@ -44,10 +46,19 @@ impl Default for Style {
 
 struct IsOpenState(bool);
 
+/// When set, related diagnostic paths are drawn as nested flame bars instead of
+/// one line graph per leaf.
+struct FlameState(bool);
+
+/// Height of a single span row in the flame-bar view.
+const BAR_HEIGHT: f32 = 18.0;
+
 #[allow(clippy::needless_pass_by_value)]
 fn plot_diagnostics_system(
     state: Res<'_, DiagnosticVisualizerState>,
     style: Res<'_, Style>,
+    mut paused: ResMut<'_, Paused>,
+    mut flame_state: ResMut<'_, FlameState>,
     mut is_open_state: ResMut<'_, IsOpenState>,
     mut egui_context: ResMut<'_, EguiContext>,
 ) {
@@ -55,16 +66,153 @@ fn plot_diagnostics_system(
         .open(&mut is_open_state.0)
         .vscroll(true)
         .show(egui_context.ctx_mut(), |ui| {
+            ui.checkbox(&mut paused.0, "Pause");
+            ui.checkbox(&mut flame_state.0, "Flame graph");
+            let mut tree = DiagnosticTree::default();
             for diagnostic_state in state.diagnostic_states.values() {
-                plot_diagnostic(diagnostic_state, ui, &style);
+                tree.insert(diagnostic_state);
+            }
+            if flame_state.0 {
+                plot_flame(&tree, ui, &style);
+            } else {
+                plot_tree(&tree, ui, &style);
             }
         });
 }
 
-fn plot_diagnostic(diagnostic_state: &DiagnosticState, ui: &mut Ui, style: &Style) {
-    CollapsingHeader::new(diagnostic_state.name.as_ref())
-        .default_open(true)
-        .show(ui, |ui| show_graph(ui, style, diagnostic_state));
+/// A prefix tree over `/`-separated diagnostic names.
+///
+/// Interior nodes become nested [`CollapsingHeader`]s while leaf nodes carry
+/// the [`DiagnosticState`] whose graph is drawn at the terminal position. The
+/// [`BTreeMap`] keeps sibling ordering stable regardless of insertion order.
+#[derive(Default)]
+struct DiagnosticTree<'a> {
+    children: BTreeMap<&'a str, DiagnosticTree<'a>>,
+    leaf: Option<&'a DiagnosticState>,
+}
+
+impl<'a> DiagnosticTree<'a> {
+    fn insert(&mut self, diagnostic_state: &'a DiagnosticState) {
+        let mut node = self;
+        for component in diagnostic_state.name.split('/') {
+            node = node.children.entry(component).or_default();
+        }
+        node.leaf = Some(diagnostic_state);
+    }
+
+    /// Current duration of this span: the leaf's latest sample if it carries one,
+    /// otherwise the sum of its children's durations.
+    fn duration(&self) -> f64 {
+        self.leaf.and_then(DiagnosticState::current).map_or_else(
+            || self.children.values().map(Self::duration).sum(),
+            |current| current,
+        )
+    }
+
+    /// Number of span rows below this node (0 for a leaf with no children).
+    fn depth(&self) -> usize {
+        self.children
+            .values()
+            .map(|child| 1 + child.depth())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn plot_tree(tree: &DiagnosticTree<'_>, ui: &mut Ui, style: &Style) {
+    for (component, child) in &tree.children {
+        if child.children.is_empty() {
+            if let Some(diagnostic_state) = child.leaf {
+                CollapsingHeader::new(*component)
+                    .default_open(true)
+                    .show(ui, |ui| show_graph(ui, style, diagnostic_state));
+            }
+        } else {
+            CollapsingHeader::new(*component)
+                .default_open(true)
+                .show(ui, |ui| {
+                    if let Some(diagnostic_state) = child.leaf {
+                        show_graph(ui, style, diagnostic_state);
+                    }
+                    plot_tree(child, ui, style);
+                });
+        }
+    }
+}
+
+/// Draw the tree as nested flame bars: the current sample of each related
+/// diagnostic path becomes a rectangle whose width is proportional to its
+/// duration and whose X offset is the cumulative duration of preceding
+/// siblings, with children stacked one row below their parent.
+fn plot_flame(tree: &DiagnosticTree<'_>, ui: &mut Ui, style: &Style) {
+    for (component, child) in &tree.children {
+        // Flame bars only make sense where the path actually nests. A flat
+        // leaf (e.g. `fps`) has no children to break down, so fall back to the
+        // line graph rather than normalizing incommensurable units together.
+        if child.children.is_empty() {
+            if let Some(diagnostic_state) = child.leaf {
+                CollapsingHeader::new(*component)
+                    .default_open(true)
+                    .show(ui, |ui| show_graph(ui, style, diagnostic_state));
+            }
+            continue;
+        }
+
+        let total = child.duration();
+        if total <= 0.0 {
+            continue;
+        }
+
+        CollapsingHeader::new(*component)
+            .default_open(true)
+            .show(ui, |ui| {
+                let (rect, _) = ui.allocate_exact_size(
+                    vec2(style.width, (1 + child.depth()) as f32 * BAR_HEIGHT),
+                    Sense::hover(),
+                );
+                let bar = Rect::from_min_size(rect.left_top(), vec2(style.width, BAR_HEIGHT));
+                paint_span(ui, style, component, child, bar);
+            });
+    }
+}
+
+/// Paint a single span rectangle with its label, then lay its children in the
+/// row below — widths proportional to their durations and clamped so the child
+/// row never extends past this span's right edge.
+fn paint_span(ui: &mut Ui, style: &Style, component: &str, node: &DiagnosticTree<'_>, bar: Rect) {
+    let shape = Shape::Rect(RectShape {
+        rect: bar,
+        rounding: Rounding::none(),
+        fill: Rgba::TRANSPARENT.into(),
+        stroke: style.rectangle_stroke,
+    });
+    ui.painter().add(shape);
+
+    let label = node.leaf.map_or_else(
+        || component.to_owned(),
+        |leaf| format!("{component}: {}", (leaf.formatter)(leaf.current().unwrap_or(0.0))),
+    );
+    let text: WidgetText = label.into();
+    let galley = text.into_galley(ui, Some(true), bar.width(), TextStyle::Button);
+    let text_pos = bar.left_top() + vec2(2.0, (bar.height() - galley.size().y) / 2.0);
+    galley.paint_with_fallback_color(&ui.painter().with_clip_rect(bar), text_pos, style.text_color);
+
+    let children_total: f64 = node.children.values().map(DiagnosticTree::duration).sum();
+    if children_total <= 0.0 {
+        return;
+    }
+
+    // Normalize children against whichever is larger so a parent measured below
+    // the sum of its children still contains them.
+    let span = node.duration().max(children_total);
+    let mut x = bar.left();
+    for (child_component, child) in &node.children {
+        let width =
+            ((child.duration() / span) as f32 * bar.width()).clamp(0.0, (bar.right() - x).max(0.0));
+        let child_bar = Rect::from_min_size(pos2(x, bar.bottom()), vec2(width, bar.height()));
+        paint_span(ui, style, child_component, child, child_bar);
+        x += width;
+    }
 }
 
 fn show_graph(ui: &mut Ui, style: &Style, state: &DiagnosticState) {
@@ -83,8 +231,8 @@ fn show_graph(ui: &mut Ui, style: &Style, state: &DiagnosticState) {
     ui.vertical(|ui| {
         let last_value = values.last().unwrap();
 
-        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
-        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let min = state.min().unwrap_or(0.0);
+        let max = state.max().unwrap_or(0.0);
 
         let spacing_x = ui.spacing().item_spacing.x;
 
@@ -126,6 +274,34 @@ fn show_graph(ui: &mut Ui, style: &Style, state: &DiagnosticState) {
         let path = PathShape::line(points, style.line_stroke);
         ui.painter().add(path);
 
+        // Crosshair readout: map the pointer's X back to a sample index (the
+        // inverse of the `remap` used to lay the line out) and show that
+        // sample's formatted value. Useful while paused for catching spikes.
+        if let Some(pointer) = ui.input().pointer.hover_pos() {
+            if rect.contains(pointer) {
+                let fractional = remap(pointer.x, rect.left()..=rect.right(), 0.0..=size as f32);
+                let index = (fractional as usize).min(size - 1);
+                let value = values[index];
+                let x = remap(index as f32, 0.0..=size as f32, 0.0..=style.width) + init_point.x;
+
+                let crosshair = Shape::line_segment(
+                    [pos2(x, rect.top()), pos2(x, rect.bottom())],
+                    style.line_stroke,
+                );
+                ui.painter().add(crosshair);
+
+                let text: WidgetText = formatter(value).into();
+                let galley =
+                    text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
+                let text_pos = pos2(x + spacing_x, pointer.y - galley.size().y / 2.0);
+                galley.paint_with_fallback_color(
+                    &ui.painter().with_clip_rect(outer_rect),
+                    text_pos,
+                    style.text_color,
+                );
+            }
+        }
+
         // Max value
         {
             let text: WidgetText = format!("max: {}", formatter(max)).into();
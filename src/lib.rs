@@ -29,6 +29,8 @@
 
 #[cfg(feature = "bevy_egui")]
 mod egui;
+#[cfg(feature = "log")]
+mod log;
 
 use bevy::{
     diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
@@ -44,8 +46,13 @@ use std::{
 pub struct DiagnosticVisualizerPlugin {
     wait_duration: Duration,
     filter: DiagnosticIds,
+    debug: bool,
+    history_length: usize,
 }
 
+/// Default number of samples retained per diagnostic.
+const DEFAULT_HISTORY_LENGTH: usize = 100;
+
 #[derive(Clone)]
 enum DiagnosticIds {
     Include(HashSet<DiagnosticId>),
@@ -70,6 +77,8 @@ impl Default for DiagnosticVisualizerPlugin {
                     .into_iter()
                     .collect(),
             ),
+            debug: false,
+            history_length: DEFAULT_HISTORY_LENGTH,
         }
     }
 }
@@ -82,6 +91,20 @@ impl DiagnosticVisualizerPlugin {
         self
     }
 
+    /// How many samples to retain per diagnostic.
+    #[must_use]
+    pub fn history_length(mut self, history_length: usize) -> Self {
+        self.history_length = history_length;
+        self
+    }
+
+    /// Emit log-backend measurements at `debug!` instead of `info!`.
+    #[must_use]
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
     /// Include a specific diagnostic ID.
     #[must_use]
     pub fn include(mut self, diagnostic_id: DiagnosticId) -> Self {
@@ -115,11 +138,17 @@ impl DiagnosticVisualizerPlugin {
     }
 }
 
+/// Shared pause toggle: while set, [`DiagnosticVisualizerState`] stops advancing
+/// so a frozen trace can be inspected.
+#[derive(Resource, Default)]
+pub(crate) struct Paused(pub(crate) bool);
+
 /// Manage the state of the diagnostic visualizer
 #[derive(Resource)]
 struct DiagnosticVisualizerState {
     timer: Timer,
     filter: DiagnosticIds,
+    history_length: usize,
     diagnostic_states: HashMap<DiagnosticId, DiagnosticState>,
 }
 
@@ -127,14 +156,102 @@ struct DiagnosticState {
     name: Cow<'static, str>,
     suffix: Cow<'static, str>,
     measurements: VecDeque<f64>,
+    history_length: usize,
+    /// Absolute index of the front element of `measurements`.
+    front_index: usize,
+    /// Absolute index to assign to the next pushed element.
+    next_index: usize,
+    /// Running sum of `measurements`, kept for an O(1) average.
+    sum: f64,
+    /// Monotonically increasing indices whose front is the window minimum.
+    min_indices: VecDeque<usize>,
+    /// Monotonically decreasing indices whose front is the window maximum.
+    max_indices: VecDeque<usize>,
 }
 
 impl DiagnosticState {
-    fn new(diagnostic: &Diagnostic) -> Self {
+    fn new(diagnostic: &Diagnostic, history_length: usize) -> Self {
         Self {
             name: diagnostic.name.clone(),
             suffix: diagnostic.suffix.clone(),
             measurements: VecDeque::default(),
+            history_length,
+            front_index: 0,
+            next_index: 0,
+            sum: 0.0,
+            min_indices: VecDeque::default(),
+            max_indices: VecDeque::default(),
+        }
+    }
+
+    /// Push a new measurement, maintaining the windowed aggregates in O(1)
+    /// amortized time and dropping the oldest sample once the configured
+    /// history length is exceeded.
+    fn push(&mut self, value: f64) {
+        let index = self.next_index;
+        self.next_index += 1;
+        self.sum += value;
+
+        // Drop tail entries that can never again be the window minimum/maximum.
+        while self
+            .min_indices
+            .back()
+            .map_or(false, |&i| self.measurements[i - self.front_index] >= value)
+        {
+            self.min_indices.pop_back();
+        }
+        self.min_indices.push_back(index);
+        while self
+            .max_indices
+            .back()
+            .map_or(false, |&i| self.measurements[i - self.front_index] <= value)
+        {
+            self.max_indices.pop_back();
+        }
+        self.max_indices.push_back(index);
+
+        self.measurements.push_back(value);
+
+        if self.measurements.len() > self.history_length {
+            if let Some(removed) = self.measurements.pop_front() {
+                self.sum -= removed;
+                if self.min_indices.front() == Some(&self.front_index) {
+                    self.min_indices.pop_front();
+                }
+                if self.max_indices.front() == Some(&self.front_index) {
+                    self.max_indices.pop_front();
+                }
+                self.front_index += 1;
+            }
+        }
+        self.measurements.make_contiguous();
+    }
+
+    /// The most recently pushed measurement, if any.
+    fn current(&self) -> Option<f64> {
+        self.measurements.back().copied()
+    }
+
+    /// The minimum over the retained window, read from the monotonic deque.
+    fn min(&self) -> Option<f64> {
+        self.min_indices
+            .front()
+            .map(|&i| self.measurements[i - self.front_index])
+    }
+
+    /// The maximum over the retained window, read from the monotonic deque.
+    fn max(&self) -> Option<f64> {
+        self.max_indices
+            .front()
+            .map(|&i| self.measurements[i - self.front_index])
+    }
+
+    /// The average over the retained window, read from the running sum.
+    fn average(&self) -> Option<f64> {
+        if self.measurements.is_empty() {
+            None
+        } else {
+            Some(self.sum / self.measurements.len() as f64)
         }
     }
 }
@@ -144,29 +261,42 @@ impl Plugin for DiagnosticVisualizerPlugin {
         app.insert_resource(DiagnosticVisualizerState {
             timer: Timer::new(self.wait_duration, TimerMode::Repeating),
             filter: self.filter.clone(),
+            history_length: self.history_length,
             diagnostic_states: HashMap::default(),
         })
+        .init_resource::<Paused>()
         .add_system_to_stage(
             CoreStage::PreUpdate,
             update_diagnostic_visualizer_state_system,
         );
         #[cfg(feature = "bevy_egui")]
         app.add_plugin(crate::egui::DiagnosticVisualizerEguiPlugin);
+        #[cfg(feature = "log")]
+        app.add_plugin(crate::log::DiagnosticVisualizerLogPlugin {
+            wait_duration: self.wait_duration,
+            debug: self.debug,
+        });
     }
 }
 
 #[allow(clippy::needless_pass_by_value)]
 fn update_diagnostic_visualizer_state_system(
     mut state: ResMut<'_, DiagnosticVisualizerState>,
+    paused: Res<'_, Paused>,
     time: Res<'_, Time>,
     diagnostics: Res<'_, Diagnostics>,
 ) {
+    if paused.0 {
+        return;
+    }
     let DiagnosticVisualizerState {
         diagnostic_states,
         filter,
         timer,
+        history_length,
         ..
     } = state.as_mut();
+    let history_length = *history_length;
     let is_tick_finished = timer.tick(time.delta()).finished();
     if !is_tick_finished {
         return;
@@ -187,17 +317,13 @@ fn update_diagnostic_visualizer_state_system(
         .for_each(|diagnostic| {
             let state = diagnostic_states
                 .entry(diagnostic.id)
-                .or_insert_with(|| DiagnosticState::new(diagnostic));
+                .or_insert_with(|| DiagnosticState::new(diagnostic, history_length));
             track_diagnostic(diagnostic, state);
         });
 }
 
 fn track_diagnostic(diagnostic: &Diagnostic, state: &mut DiagnosticState) {
     if let Some(last) = diagnostic.average() {
-        state.measurements.push_back(last);
-        if state.measurements.len() > 100 {
-            state.measurements.pop_front();
-        }
-        state.measurements.make_contiguous();
+        state.push(last);
     }
 }
@@ -0,0 +1,58 @@
+use crate::DiagnosticVisualizerState;
+use bevy::prelude::*;
+use std::time::Duration;
+
+/// Headless backend that periodically logs the tracked measurements through
+/// [`tracing`], for use on dedicated servers or in CI where no window exists.
+pub struct DiagnosticVisualizerLogPlugin {
+    pub wait_duration: Duration,
+    pub debug: bool,
+}
+
+impl Plugin for DiagnosticVisualizerLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LogState {
+            timer: Timer::new(self.wait_duration, TimerMode::Repeating),
+            debug: self.debug,
+        })
+        .add_system_to_stage(CoreStage::PostUpdate, log_diagnostics_system);
+    }
+}
+
+#[derive(Resource)]
+struct LogState {
+    timer: Timer,
+    debug: bool,
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn log_diagnostics_system(
+    state: Res<'_, DiagnosticVisualizerState>,
+    mut log_state: ResMut<'_, LogState>,
+    time: Res<'_, Time>,
+) {
+    if !log_state.timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    for diagnostic_state in state.diagnostic_states.values() {
+        let Some(current) = diagnostic_state.current() else {
+            continue;
+        };
+        let min = diagnostic_state.min().unwrap_or(current);
+        let max = diagnostic_state.max().unwrap_or(current);
+        let average = diagnostic_state.average().unwrap_or(current);
+
+        let name = &diagnostic_state.name;
+        let suffix = &diagnostic_state.suffix;
+        if log_state.debug {
+            debug!(
+                "{name}: {current:.6}{suffix} (min: {min:.6}, max: {max:.6}, avg: {average:.6})"
+            );
+        } else {
+            info!(
+                "{name}: {current:.6}{suffix} (min: {min:.6}, max: {max:.6}, avg: {average:.6})"
+            );
+        }
+    }
+}